@@ -1,21 +1,60 @@
 //! Provides a simple async client for the ChainStream API.
-use std::{sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use anyhow::Result;
+use futures_core::Stream;
 use jsonrpsee::{
     core::client::{Subscription, SubscriptionClientT},
     http_client::HeaderMap,
     ws_client::{PingConfig, WsClient, WsClientBuilder},
 };
 use serde::de::DeserializeOwned;
+use solana_sdk::signature::Signature;
+
+use crate::raydium::{anchor_events::RaydiumCLMMEvent, parse::parse_raydium_anchor_events};
 
-use super::methods::Method;
+use super::{methods::Method, types::transaction::TransactionWrite};
 
 pub type ChainStreamSubscription<T> = Subscription<T>;
 
+/// Controls how a [`ReconnectingSubscription`] backs off between reconnect attempts after the
+/// underlying connection drops.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay after the first failed reconnect attempt. The first attempt is made as soon as the
+    /// stream ends.
+    pub initial_backoff: Duration,
+    /// Upper bound the exponential backoff is clamped to.
+    pub max_backoff: Duration,
+    /// Maximum number of consecutive failed attempts before giving up. `None` retries forever.
+    pub max_retries: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_retries: None,
+        }
+    }
+}
+
+/// Default per-receiver queue capacity for [`ChainStreamClient::subscribe_shared`].
+const DEFAULT_BROADCAST_CAPACITY: usize = 1024;
+
+#[derive(Clone)]
 pub struct ClientBuilder {
     token: String,
     ws_client_builder: WsClientBuilder,
+    reconnect_policy: ReconnectPolicy,
+    broadcast_capacity: usize,
 }
 
 pub type ClientError = jsonrpsee::core::ClientError;
@@ -25,6 +64,8 @@ impl ClientBuilder {
         let builder = Self {
             token: token.to_string(),
             ws_client_builder: WsClientBuilder::default(),
+            reconnect_policy: ReconnectPolicy::default(),
+            broadcast_capacity: DEFAULT_BROADCAST_CAPACITY,
         };
         builder.token(token)
     }
@@ -36,6 +77,26 @@ impl ClientBuilder {
         Self {
             token: token.to_string(),
             ws_client_builder: self.ws_client_builder.set_headers(headers),
+            ..self
+        }
+    }
+
+    /// Configure the backoff policy used when reconnecting a [`ReconnectingSubscription`].
+    /// Defaults to a 500ms initial backoff doubling up to 30s, retried indefinitely.
+    pub fn reconnect_policy(self, policy: ReconnectPolicy) -> Self {
+        Self {
+            reconnect_policy: policy,
+            ..self
+        }
+    }
+
+    /// Per-receiver queue capacity used by [`ChainStreamClient::subscribe_shared`] (default
+    /// 1024). A receiver that falls behind the broadcast by more than this many items lags and
+    /// skips forward rather than unboundedly buffering.
+    pub fn broadcast_capacity(self, capacity: usize) -> Self {
+        Self {
+            broadcast_capacity: capacity,
+            ..self
         }
     }
 
@@ -128,8 +189,25 @@ impl ClientBuilder {
         Ok(ChainStreamClient {
             inner: Arc::new(self.ws_client_builder.build(url).await?),
             token: self.token,
+            broadcast_capacity: self.broadcast_capacity,
         })
     }
+
+    /// Builds a client against `url` and opens an auto-reconnecting subscription for `method`.
+    ///
+    /// Unlike [`ChainStreamClient::subscribe`], the returned [`ReconnectingSubscription`]
+    /// transparently rebuilds the `WsClient` and re-issues the subscribe call with backoff
+    /// whenever the stream ends or the transport errors, instead of yielding `None` for good.
+    pub async fn build_reconnecting<T>(
+        self,
+        url: &str,
+        method: Method,
+    ) -> Result<ReconnectingSubscription<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        ReconnectingSubscription::connect(self, url.to_string(), method).await
+    }
 }
 
 #[derive(Debug)]
@@ -138,6 +216,8 @@ pub struct ChainStreamClient {
 
     #[allow(dead_code)]
     token: String,
+
+    broadcast_capacity: usize,
 }
 
 impl ChainStreamClient {
@@ -152,6 +232,7 @@ impl ChainStreamClient {
         Ok(Self {
             inner: Arc::new(WsClientBuilder::new().set_headers(map).build(url).await?),
             token: token.to_string(),
+            broadcast_capacity: DEFAULT_BROADCAST_CAPACITY,
         })
     }
 
@@ -171,4 +252,322 @@ impl ChainStreamClient {
 
         Ok(subscription)
     }
+
+    /// Opens one upstream subscription for `method` and fans it out to any number of
+    /// independent consumers via a [`SharedSubscription`], instead of each caller opening its
+    /// own WS subscription.
+    pub async fn subscribe_shared<T>(&self, method: Method) -> Result<SharedSubscription<T>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+    {
+        let mut inner = self.subscribe::<T>(method).await?;
+        let (task_sender, receiver) = tokio::sync::broadcast::channel(self.broadcast_capacity);
+
+        // The background task owns the only `Sender`, so receivers see the channel close once
+        // the upstream subscription ends and the task exits.
+        tokio::spawn(async move {
+            while let Some(item) = inner.next().await {
+                match item {
+                    // `send` errors only when there are zero receivers, i.e. once the
+                    // `SharedSubscription` handle and every receiver have been dropped; the
+                    // error is ignored and the task keeps draining the upstream subscription.
+                    Ok(item) => {
+                        let _ = task_sender.send(item);
+                    }
+                    Err(err) => {
+                        eprintln!("[chainstream] error decoding shared subscription item: {err}");
+                    }
+                }
+            }
+        });
+
+        Ok(SharedSubscription { receiver })
+    }
+
+    /// Subscribes to `TransactionWrite`s for `program_id` and parses each one into Raydium
+    /// anchor events, collapsing the subscribe -> parse -> match boilerplate duplicated across
+    /// the example binaries into a single stream. Transactions with no matching events are
+    /// skipped, and parse errors surface as stream items instead of being `eprintln!`-ed away.
+    pub async fn subscribe_events(
+        &self,
+        program_id: &'static str,
+        method: Method,
+    ) -> Result<EventSubscription> {
+        let inner = self.subscribe::<TransactionWrite>(method).await?;
+        Ok(EventSubscription {
+            inner,
+            program_id,
+            filter: None,
+        })
+    }
+}
+
+/// A [`TransactionWrite`] subscription fused with Raydium anchor-event parsing, returned by
+/// [`ChainStreamClient::subscribe_events`]. Implements [`Stream`], so it composes with
+/// `StreamExt` combinators as well as plain `while let Some(item) = events.next().await` loops.
+pub struct EventSubscription {
+    inner: ChainStreamSubscription<TransactionWrite>,
+    program_id: &'static str,
+    filter: Option<Box<dyn Fn(&RaydiumCLMMEvent) -> bool + Send>>,
+}
+
+impl EventSubscription {
+    /// Only yield events matching `predicate`, e.g. `|e| matches!(e, RaydiumCLMMEvent::Swap(_))`
+    /// to watch swaps only.
+    pub fn filter_events(
+        mut self,
+        predicate: impl Fn(&RaydiumCLMMEvent) -> bool + Send + 'static,
+    ) -> Self {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    /// Yields the signature and parsed events for the next transaction with at least one
+    /// matching event, skipping transactions that have none. Equivalent to polling this as a
+    /// [`Stream`].
+    pub async fn next(&mut self) -> Option<Result<(Signature, Vec<RaydiumCLMMEvent>)>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl Stream for EventSubscription {
+    type Item = Result<(Signature, Vec<RaydiumCLMMEvent>)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let transaction = match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(transaction))) => transaction,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let events =
+                match parse_raydium_anchor_events(this.program_id, transaction.meta().clone()) {
+                    Ok(events) => events,
+                    Err(err) => {
+                        return Poll::Ready(Some(Err(anyhow::anyhow!(
+                            "failed to parse raydium events: {err}"
+                        ))))
+                    }
+                };
+
+            let events: Vec<_> = match &this.filter {
+                Some(predicate) => events
+                    .into_iter()
+                    .filter(|event| predicate(event))
+                    .collect(),
+                None => events,
+            };
+
+            if events.is_empty() {
+                continue;
+            }
+
+            return Poll::Ready(Some(Ok((transaction.signature(), events))));
+        }
+    }
+}
+
+/// A single upstream [`ChainStreamSubscription`] rebroadcast to any number of independent
+/// consumers, so N callers interested in the same [`Method`] only cost one upstream
+/// subscription.
+pub struct SharedSubscription<T> {
+    receiver: tokio::sync::broadcast::Receiver<T>,
+}
+
+impl<T> SharedSubscription<T>
+where
+    T: Clone,
+{
+    /// Opens an independent receiver onto the shared stream. Each receiver has its own queue,
+    /// sized by [`ClientBuilder::broadcast_capacity`]; one that falls behind skips forward and
+    /// reports how many items it missed.
+    pub fn subscribe(&self) -> SharedSubscriptionReceiver<T> {
+        SharedSubscriptionReceiver {
+            inner: self.receiver.resubscribe(),
+        }
+    }
+}
+
+pub struct SharedSubscriptionReceiver<T> {
+    inner: tokio::sync::broadcast::Receiver<T>,
+}
+
+impl<T> SharedSubscriptionReceiver<T>
+where
+    T: Clone,
+{
+    /// Yields the next item. Returns `Some(Err(RecvError::Lagged(n)))` if this receiver fell
+    /// behind and skipped `n` items, or `None` once the upstream subscription has ended for
+    /// good.
+    pub async fn next(&mut self) -> Option<Result<T, tokio::sync::broadcast::error::RecvError>> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        match self.inner.recv().await {
+            Ok(item) => Some(Ok(item)),
+            Err(RecvError::Closed) => None,
+            Err(err @ RecvError::Lagged(_)) => Some(Err(err)),
+        }
+    }
+}
+
+type ConnectFuture<T> = Pin<Box<dyn Future<Output = Result<ChainStreamSubscription<T>>> + Send>>;
+
+/// What a [`ReconnectingSubscription`] is doing right now.
+enum ReconnectStep<T> {
+    /// Forwarding items straight from the live subscription.
+    Streaming,
+    /// Rebuilding the `WsClient` and re-issuing the subscribe call.
+    Connecting(ConnectFuture<T>),
+    /// Backing off before the next connect attempt.
+    Sleeping(Pin<Box<tokio::time::Sleep>>),
+}
+
+/// A [`ChainStreamSubscription`] wrapper that transparently reconnects when the underlying
+/// WebSocket connection drops, instead of ending the stream.
+///
+/// It owns everything needed to rebuild the connection from scratch (the [`ClientBuilder`]
+/// config, the target URL and the [`Method`]), so callers can keep driving it with
+/// `while let Some(item) = sub.next().await` or any `Stream`/`StreamExt` combinator across
+/// reconnects. A reconnect is logged to stderr so callers can detect the gap in the stream. If
+/// the configured [`ReconnectPolicy::max_retries`] is exhausted, the give-up error is surfaced
+/// as one final `Some(Err(..))` before the stream ends, rather than silently returning `None`.
+pub struct ReconnectingSubscription<T> {
+    builder: ClientBuilder,
+    url: String,
+    method: Method,
+    inner: ChainStreamSubscription<T>,
+    step: ReconnectStep<T>,
+    attempt: u32,
+    backoff: Duration,
+    gave_up: bool,
+}
+
+impl<T> ReconnectingSubscription<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn connect(builder: ClientBuilder, url: String, method: Method) -> Result<Self> {
+        let inner = Self::open(builder.clone(), url.clone(), method.clone()).await?;
+        let backoff = builder.reconnect_policy.initial_backoff;
+        Ok(Self {
+            builder,
+            url,
+            method,
+            inner,
+            step: ReconnectStep::Streaming,
+            attempt: 0,
+            backoff,
+            gave_up: false,
+        })
+    }
+
+    async fn open(
+        builder: ClientBuilder,
+        url: String,
+        method: Method,
+    ) -> Result<ChainStreamSubscription<T>> {
+        let client = builder.build(&url).await?;
+        client.subscribe(method).await
+    }
+
+    fn connecting(builder: &ClientBuilder, url: &str, method: &Method) -> ConnectFuture<T> {
+        Box::pin(Self::open(builder.clone(), url.to_string(), method.clone()))
+    }
+
+    /// Yields the next item, transparently reconnecting (per the configured
+    /// [`ReconnectPolicy`]) whenever the subscription ends or the transport errors. Equivalent
+    /// to polling this as a [`Stream`].
+    pub async fn next(&mut self) -> Option<Result<T>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T> Stream for ReconnectingSubscription<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.gave_up {
+                return Poll::Ready(None);
+            }
+
+            // Take the current step so transitioning it doesn't conflict with borrowing `this`
+            // elsewhere (e.g. to build the next connect future).
+            let mut step = std::mem::replace(&mut this.step, ReconnectStep::Streaming);
+
+            match &mut step {
+                ReconnectStep::Streaming => match Pin::new(&mut this.inner).poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        this.step = step;
+                        return Poll::Ready(Some(item.map_err(anyhow::Error::from)));
+                    }
+                    Poll::Ready(None) => {
+                        this.attempt = 0;
+                        this.backoff = this.builder.reconnect_policy.initial_backoff;
+                        this.step = ReconnectStep::Connecting(Self::connecting(
+                            &this.builder,
+                            &this.url,
+                            &this.method,
+                        ));
+                    }
+                    Poll::Pending => {
+                        this.step = step;
+                        return Poll::Pending;
+                    }
+                },
+                ReconnectStep::Connecting(fut) => match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(inner)) => {
+                        let attempt = this.attempt + 1;
+                        this.inner = inner;
+                        this.attempt = 0;
+                        this.step = ReconnectStep::Streaming;
+                        eprintln!(
+                            "[chainstream] reconnected after {attempt} attempt(s); a gap in the stream is possible"
+                        );
+                    }
+                    Poll::Ready(Err(err)) => {
+                        this.attempt += 1;
+                        let attempt = this.attempt;
+                        let policy = this.builder.reconnect_policy.clone();
+                        if policy.max_retries.is_some_and(|max| attempt >= max) {
+                            this.gave_up = true;
+                            return Poll::Ready(Some(Err(err.context(format!(
+                                "giving up reconnecting after {attempt} attempt(s)"
+                            )))));
+                        }
+                        let sleep_for = this.backoff;
+                        this.backoff = std::cmp::min(this.backoff * 2, policy.max_backoff);
+                        this.step =
+                            ReconnectStep::Sleeping(Box::pin(tokio::time::sleep(sleep_for)));
+                    }
+                    Poll::Pending => {
+                        this.step = step;
+                        return Poll::Pending;
+                    }
+                },
+                ReconnectStep::Sleeping(sleep) => match sleep.as_mut().poll(cx) {
+                    Poll::Ready(()) => {
+                        this.step = ReconnectStep::Connecting(Self::connecting(
+                            &this.builder,
+                            &this.url,
+                            &this.method,
+                        ));
+                    }
+                    Poll::Pending => {
+                        this.step = step;
+                        return Poll::Pending;
+                    }
+                },
+            }
+        }
+    }
 }
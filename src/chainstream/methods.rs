@@ -1,6 +1,6 @@
 //! Provides the necessary types required to build Chainstream RPC requests.
 use jsonrpsee::core::params::{self, ObjectParams};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json;
 use thiserror;
 
@@ -19,6 +19,46 @@ impl Network {
     }
 }
 
+/// The commitment level at which a subscription observes the chain, trading latency for
+/// finality guarantees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    /// The most recent slot the node has processed. Fastest, but may be rolled back.
+    Processed,
+    /// A supermajority of the cluster has voted on this slot. Rarely rolled back.
+    Confirmed,
+    /// A supermajority of the cluster has recognized this slot as rooted. Never rolled back.
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+}
+
+/// How account data is encoded in the subscription payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoding {
+    Base64,
+    JsonParsed,
+}
+
+impl Encoding {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Encoding::Base64 => "base64",
+            Encoding::JsonParsed => "jsonParsed",
+        }
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum RpcError {
     #[error("Unsupported method")]
@@ -27,7 +67,7 @@ pub enum RpcError {
     ParamsError(String),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Method {
     #[serde(rename = "transactionsSubscribe")]
     TransactionSubscribe(TransactionMethodBuilder),
@@ -35,6 +75,12 @@ pub enum Method {
     BlockSubscribe(BlockMethodBuilder),
     #[serde(rename = "slotUpdatesSubscribe")]
     SlotSubscribe(SlotMethodBuilder),
+    #[serde(rename = "accountsSubscribe")]
+    AccountSubscribe(AccountMethodBuilder),
+    #[serde(rename = "programSubscribe")]
+    ProgramSubscribe(ProgramMethodBuilder),
+    #[serde(rename = "logsSubscribe")]
+    LogsSubscribe(LogsMethodBuilder),
 }
 
 impl Method {
@@ -50,11 +96,26 @@ impl Method {
         SlotMethodBuilder::default()
     }
 
+    pub fn new_account_subscription() -> AccountMethodBuilder {
+        AccountMethodBuilder::default()
+    }
+
+    pub fn new_program_subscription() -> ProgramMethodBuilder {
+        ProgramMethodBuilder::default()
+    }
+
+    pub fn new_logs_subscription() -> LogsMethodBuilder {
+        LogsMethodBuilder::default()
+    }
+
     pub fn params(&self) -> Result<params::ObjectParams, RpcError> {
         match self {
             Method::TransactionSubscribe(builder) => builder.build_params(),
             Method::BlockSubscribe(builder) => builder.build_params(),
             Method::SlotSubscribe(builder) => builder.build_params(),
+            Method::AccountSubscribe(builder) => builder.build_params(),
+            Method::ProgramSubscribe(builder) => builder.build_params(),
+            Method::LogsSubscribe(builder) => builder.build_params(),
         }
     }
 
@@ -63,6 +124,9 @@ impl Method {
             Method::TransactionSubscribe(_) => "transactionsSubscribe",
             Method::BlockSubscribe(_) => "blocksSubscribe",
             Method::SlotSubscribe(_) => "slotUpdatesSubscribe",
+            Method::AccountSubscribe(_) => "accountsSubscribe",
+            Method::ProgramSubscribe(_) => "programSubscribe",
+            Method::LogsSubscribe(_) => "logsSubscribe",
         }
     }
 
@@ -71,6 +135,9 @@ impl Method {
             Method::TransactionSubscribe(_) => "transactionsUnsubscribe",
             Method::BlockSubscribe(_) => "blocksUnsubscribe",
             Method::SlotSubscribe(_) => "slotUpdatesUnsubscribe",
+            Method::AccountSubscribe(_) => "accountsUnsubscribe",
+            Method::ProgramSubscribe(_) => "programUnsubscribe",
+            Method::LogsSubscribe(_) => "logsUnsubscribe",
         }
     }
 }
@@ -81,6 +148,8 @@ pub struct TransactionMethodBuilder {
     pub network: Network,
     pub verified: bool,
     pub filter: TransactionFilter,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
 }
 
 impl TransactionMethodBuilder {
@@ -96,6 +165,13 @@ impl TransactionMethodBuilder {
         Self { verified, ..self }
     }
 
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
     pub fn exclude_votes(self, exclude_votes: bool) -> Self {
         let filter = TransactionFilter {
             exclude_votes: Some(exclude_votes),
@@ -148,6 +224,11 @@ impl TransactionMethodBuilder {
         params
             .insert("filter", serde_json::to_value(&self.filter).unwrap())
             .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
 
         Ok(params)
     }
@@ -166,6 +247,7 @@ impl Default for TransactionMethodBuilder {
                 exclude_votes: None,
                 account_keys: None,
             },
+            commitment: None,
         }
     }
 }
@@ -195,6 +277,8 @@ struct PubKeySelector {
 pub struct BlockMethodBuilder {
     pub network: Network,
     pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
 }
 
 impl BlockMethodBuilder {
@@ -206,6 +290,13 @@ impl BlockMethodBuilder {
         Self { verified, ..self }
     }
 
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
     pub fn build_params(&self) -> Result<ObjectParams, RpcError> {
         let mut params = params::ObjectParams::new();
         params
@@ -214,6 +305,11 @@ impl BlockMethodBuilder {
         params
             .insert("verified", self.verified)
             .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
 
         Ok(params)
     }
@@ -228,6 +324,7 @@ impl Default for BlockMethodBuilder {
         Self {
             network: Network::SolanaMainnet,
             verified: false,
+            commitment: None,
         }
     }
 }
@@ -237,6 +334,8 @@ impl Default for BlockMethodBuilder {
 pub struct SlotMethodBuilder {
     pub network: Network,
     pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
 }
 
 impl SlotMethodBuilder {
@@ -248,6 +347,13 @@ impl SlotMethodBuilder {
         Self { verified, ..self }
     }
 
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
     pub fn build_params(&self) -> Result<ObjectParams, RpcError> {
         let mut params = params::ObjectParams::new();
         params
@@ -256,6 +362,11 @@ impl SlotMethodBuilder {
         params
             .insert("verified", self.verified)
             .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
 
         Ok(params)
     }
@@ -270,6 +381,336 @@ impl Default for SlotMethodBuilder {
         Self {
             network: Network::SolanaMainnet,
             verified: false,
+            commitment: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountMethodBuilder {
+    pub network: Network,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    pub accounts: Vec<String>,
+    pub encoding: Encoding,
+}
+
+impl AccountMethodBuilder {
+    pub fn network(self, network: Network) -> Self {
+        Self { network, ..self }
+    }
+
+    pub fn verified(self, verified: bool) -> Self {
+        Self { verified, ..self }
+    }
+
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
+    /// The account pubkeys to watch. Subscribing to more than one pubkey yields updates for any
+    /// of them.
+    pub fn accounts(self, accounts: Vec<String>) -> Self {
+        Self { accounts, ..self }
+    }
+
+    pub fn encoding(self, encoding: Encoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    pub fn build_params(&self) -> Result<ObjectParams, RpcError> {
+        let mut params = params::ObjectParams::new();
+        params
+            .insert("network", self.network.as_str())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("verified", self.verified)
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("accounts", &self.accounts)
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("encoding", self.encoding.as_str())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
+
+        Ok(params)
+    }
+
+    pub fn build(self) -> Method {
+        Method::AccountSubscribe(self)
+    }
+}
+
+impl Default for AccountMethodBuilder {
+    fn default() -> Self {
+        Self {
+            network: Network::SolanaMainnet,
+            verified: false,
+            commitment: None,
+            accounts: Vec::new(),
+            encoding: Encoding::Base64,
+        }
+    }
+}
+
+/// A single data filter applied to a [`ProgramMethodBuilder`] subscription. Accounts must match
+/// every filter in the list to be included in the stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgramFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProgramMethodBuilder {
+    pub network: Network,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    pub program_id: String,
+    pub encoding: Encoding,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<ProgramFilter>>,
+}
+
+impl ProgramMethodBuilder {
+    pub fn network(self, network: Network) -> Self {
+        Self { network, ..self }
+    }
+
+    pub fn verified(self, verified: bool) -> Self {
+        Self { verified, ..self }
+    }
+
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
+    pub fn program_id(self, program_id: impl Into<String>) -> Self {
+        Self {
+            program_id: program_id.into(),
+            ..self
+        }
+    }
+
+    pub fn encoding(self, encoding: Encoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    /// Only include accounts whose data is exactly `size` bytes.
+    pub fn data_size(self, size: u64) -> Self {
+        let mut filters = self.filters.unwrap_or_default();
+        filters.push(ProgramFilter::DataSize(size));
+        Self {
+            filters: Some(filters),
+            ..self
+        }
+    }
+
+    /// Only include accounts whose data matches `bytes` (base58) at the given byte `offset`.
+    pub fn memcmp(self, offset: usize, bytes: impl Into<String>) -> Self {
+        let mut filters = self.filters.unwrap_or_default();
+        filters.push(ProgramFilter::Memcmp {
+            offset,
+            bytes: bytes.into(),
+        });
+        Self {
+            filters: Some(filters),
+            ..self
+        }
+    }
+
+    pub fn build_params(&self) -> Result<ObjectParams, RpcError> {
+        let mut params = params::ObjectParams::new();
+        params
+            .insert("network", self.network.as_str())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("verified", self.verified)
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("programId", &self.program_id)
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("encoding", self.encoding.as_str())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
+        if let Some(filters) = &self.filters {
+            params
+                .insert("filters", serde_json::to_value(filters).unwrap())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
+
+        Ok(params)
+    }
+
+    pub fn build(self) -> Method {
+        Method::ProgramSubscribe(self)
+    }
+}
+
+impl Default for ProgramMethodBuilder {
+    fn default() -> Self {
+        Self {
+            network: Network::SolanaMainnet,
+            verified: false,
+            commitment: None,
+            program_id: String::new(),
+            encoding: Encoding::Base64,
+            filters: None,
+        }
+    }
+}
+
+/// Which transaction logs a `logsSubscribe` stream should include. Serializes to the
+/// polymorphic shape Solana's pubsub API expects: the bare string `"all"`/`"allWithVotes"`, or
+/// `{ "mentions": [..] }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogsFilter {
+    All,
+    AllWithVotes,
+    Mentions(Vec<String>),
+}
+
+impl Serialize for LogsFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            LogsFilter::All => serializer.serialize_str("all"),
+            LogsFilter::AllWithVotes => serializer.serialize_str("allWithVotes"),
+            LogsFilter::Mentions(accounts) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("mentions", accounts)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LogsFilter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) if s == "all" => Ok(LogsFilter::All),
+            serde_json::Value::String(s) if s == "allWithVotes" => Ok(LogsFilter::AllWithVotes),
+            serde_json::Value::Object(mut obj) => {
+                let mentions = obj
+                    .remove("mentions")
+                    .ok_or_else(|| D::Error::missing_field("mentions"))?;
+                let mentions: Vec<String> =
+                    serde_json::from_value(mentions).map_err(D::Error::custom)?;
+                Ok(LogsFilter::Mentions(mentions))
+            }
+            other => Err(D::Error::custom(format!("invalid logs filter: {other}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LogsMethodBuilder {
+    pub network: Network,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    pub filter: LogsFilter,
+}
+
+impl LogsMethodBuilder {
+    pub fn network(self, network: Network) -> Self {
+        Self { network, ..self }
+    }
+
+    pub fn verified(self, verified: bool) -> Self {
+        Self { verified, ..self }
+    }
+
+    pub fn commitment(self, commitment: Commitment) -> Self {
+        Self {
+            commitment: Some(commitment),
+            ..self
+        }
+    }
+
+    /// Stream logs from every non-vote transaction.
+    pub fn all(self) -> Self {
+        Self {
+            filter: LogsFilter::All,
+            ..self
+        }
+    }
+
+    /// Stream logs from every transaction, including vote transactions.
+    pub fn all_with_votes(self) -> Self {
+        Self {
+            filter: LogsFilter::AllWithVotes,
+            ..self
+        }
+    }
+
+    /// Only stream logs for transactions that mention one of `accounts`.
+    pub fn mentions(self, accounts: Vec<String>) -> Self {
+        Self {
+            filter: LogsFilter::Mentions(accounts),
+            ..self
+        }
+    }
+
+    pub fn build_params(&self) -> Result<ObjectParams, RpcError> {
+        let mut params = params::ObjectParams::new();
+        params
+            .insert("network", self.network.as_str())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("verified", self.verified)
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        params
+            .insert("filter", serde_json::to_value(&self.filter).unwrap())
+            .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        if let Some(commitment) = self.commitment {
+            params
+                .insert("commitment", commitment.as_str())
+                .map_err(|e| RpcError::ParamsError(e.to_string()))?;
+        }
+
+        Ok(params)
+    }
+
+    pub fn build(self) -> Method {
+        Method::LogsSubscribe(self)
+    }
+}
+
+impl Default for LogsMethodBuilder {
+    fn default() -> Self {
+        Self {
+            network: Network::SolanaMainnet,
+            verified: false,
+            commitment: None,
+            filter: LogsFilter::All,
         }
     }
 }